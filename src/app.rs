@@ -2,17 +2,19 @@ use crate::cli::parser::Args;
 use crate::commands::{ChatState, dispatcher::CommandDispatcher};
 use crate::config::Config;
 use crate::core::error::SchatError;
-use crate::core::executor::execute_command;
-use crate::display::{self, UserChoice};
+use crate::core::executor::{execute_command, preview_command};
+use crate::core::script::{parse_script_steps, render_script, save_script};
+use crate::display::{self, PlanChoice, StepChoice, UserChoice};
 use crate::input;
 use crate::mcp::{Tool, ToolSet, tool::get_mcp_tools};
 use crate::providers::{LLMProvider, Message, Role};
-use crate::system::SystemInfo;
+use crate::system::{EnvironmentContext, SystemInfo};
 use console;
 use futures::StreamExt;
 use is_terminal::IsTerminal;
 use regex;
 use std::io::{self, Read, Write};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub struct Application {
@@ -50,7 +52,15 @@ impl Application {
             None
         };
 
-        if self.args.shell {
+        if self.args.show_context && self.args.query.is_none() && context.is_none() {
+            let env_context = EnvironmentContext::collect(&self.config.context);
+            display::display_context(&env_context.to_prompt());
+            return Ok(());
+        }
+
+        if self.args.script {
+            self.handle_script_mode(&system_info, context).await?;
+        } else if self.args.shell {
             self.handle_shell_mode(&system_info, context).await?;
         } else if self.args.chat {
             self.handle_continuous_chat_mode().await?;
@@ -61,14 +71,31 @@ impl Application {
         Ok(())
     }
 
+    /// Fills in a shell-mode system prompt template and appends the environment context.
+    fn shell_system_prompt(&self, template: &str, system_info: &SystemInfo) -> String {
+        let mut prompt = template
+            .replace("{shell}", &system_info.shell_path)
+            .replace("{os_info}", &system_info.os_info);
+
+        let env_context = EnvironmentContext::collect(&self.config.context).to_prompt();
+        if self.args.show_context {
+            display::display_context(&env_context);
+        }
+        if !env_context.is_empty() {
+            prompt.push_str(&format!(
+                "\n\nAdditional information about the environment:\n{}",
+                env_context
+            ));
+        }
+        prompt
+    }
+
     async fn handle_shell_mode(
         &self,
         system_info: &SystemInfo,
         context: Option<String>,
     ) -> Result<(), SchatError> {
-        let prompt = SYSTEM_PROMPT_FOR_SHELL
-            .replace("{shell}", &system_info.shell_path)
-            .replace("{os_info}", &system_info.os_info);
+        let prompt = self.shell_system_prompt(SYSTEM_PROMPT_FOR_SHELL, system_info);
 
         let final_query = match (self.args.query.as_deref(), context) {
             (Some(arg_q), Some(stdin_ctx)) => format!("<pipe>{}</pipe>\n\n{}", stdin_ctx, arg_q),
@@ -96,6 +123,11 @@ impl Application {
 
         display::display_command(&command);
 
+        if self.args.dry_run {
+            preview_command(&command, system_info, &self.config.sandbox)?;
+            return Ok(());
+        }
+
         enum State {
             Initial,
             AfterFirstDescribe(Vec<Message>),
@@ -112,6 +144,9 @@ impl Application {
                 UserChoice::Execute => {
                     execute = true;
                 }
+                UserChoice::Preview => {
+                    preview_command(&command, system_info, &self.config.sandbox)?;
+                }
                 UserChoice::Describe => {
                     let mut current_messages = match &state {
                         State::Initial => messages.clone(),
@@ -159,6 +194,99 @@ impl Application {
         Ok(())
     }
 
+    async fn handle_script_mode(
+        &self,
+        system_info: &SystemInfo,
+        context: Option<String>,
+    ) -> Result<(), SchatError> {
+        let prompt = self.shell_system_prompt(SYSTEM_PROMPT_FOR_SCRIPT, system_info);
+
+        let final_query = match (self.args.query.as_deref(), context) {
+            (Some(arg_q), Some(stdin_ctx)) => format!("<pipe>{}</pipe>\n\n{}", stdin_ctx, arg_q),
+            (None, Some(stdin_ctx)) => format!("<pipe>{}</pipe>", stdin_ctx),
+            (Some(arg_q), None) => arg_q.to_string(),
+            (None, None) => {
+                return Err(SchatError::Input(
+                    "Query argument missing for script mode".to_string(),
+                ));
+            }
+        };
+        let messages = vec![
+            Message {
+                role: Role::System,
+                content: prompt,
+            },
+            Message {
+                role: Role::User,
+                content: final_query,
+            },
+        ];
+
+        let raw_response = self.provider.get_response(&messages).await?;
+        let mut steps = parse_script_steps(&raw_response);
+        if steps.is_empty() {
+            return Err(SchatError::Api(
+                "The model did not return any script steps".to_string(),
+            ));
+        }
+
+        display::display_script_plan(&steps);
+
+        if self.args.dry_run {
+            let script = render_script(&steps, &system_info.shell_type);
+            preview_command(&script, system_info, &self.config.sandbox)?;
+            return Ok(());
+        }
+
+        let auto_confirm = self.args.yes || self.config.auto_confirm;
+        if !auto_confirm {
+            match display::prompt_plan_confirmation() {
+                PlanChoice::Run => {}
+                PlanChoice::Save => {
+                    let default_name =
+                        format!("script.{}", system_info.shell_type.script_extension());
+                    if let Some(name) = display::prompt_edit("Save as:", &default_name) {
+                        let path = PathBuf::from(name);
+                        save_script(&steps, &system_info.shell_type, &path)?;
+                        println!("Script saved to: {}", path.display());
+                    }
+                    return Ok(());
+                }
+                PlanChoice::Abort => return Ok(()),
+            }
+        }
+
+        let total = steps.len();
+        for (index, step) in steps.iter_mut().enumerate() {
+            display::display_script_step(index, total, step);
+
+            let mut run = auto_confirm;
+            while !run {
+                match display::prompt_step_confirmation() {
+                    StepChoice::Run => run = true,
+                    StepChoice::Skip => break,
+                    StepChoice::Edit => {
+                        if let Some(edited) = display::prompt_edit(">", &step.command) {
+                            step.command = edited;
+                        }
+                    }
+                    StepChoice::Abort => return Ok(()),
+                }
+            }
+            if !run {
+                continue;
+            }
+
+            let status = execute_command(&step.command, system_info)?;
+            if !status.success() {
+                display::display_step_failure(index, status.code());
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
     async fn generate_ai_response(&self, state: &ChatState) -> Result<String, SchatError> {
         let mut stream = state.provider.get_response_stream(&state.messages).await?;
         io::stdout().flush()?;
@@ -498,6 +626,11 @@ const SYSTEM_PROMPT_FOR_SHELL: &str = "Convert the natural language query to a s
 will work on the current system. Only output the bare command without any explanation or markdown \
 formatting. Include any necessary flags to make the command compatible with the current shell and OS. \
 The current shell is {shell} and the OS is {os_info}.";
+const SYSTEM_PROMPT_FOR_SCRIPT: &str = "Break the task described by the user into an ordered list \
+of commands that will work on the current system. Respond only with a JSON array where each element \
+is an object with a \"description\" field (a short explanation of the step) and a \"command\" field \
+(a single command). Do not include markdown formatting. The current shell is {shell} and the OS is \
+{os_info}.";
 const SYSTEM_PROMPT_FOR_CHAT: &str =
     "You are a helpful assistant. Answer the following question in a concise manner: ";
 const SYSTEM_PROMPT_FOR_DESCRIBE: &str = "Explain the shell command that was just provided in a concise \
@@ -10,6 +10,14 @@ pub struct Args {
     #[arg(short, long)]
     pub shell: bool,
 
+    /// Generate a multi-step script and execute it step by step
+    #[arg(long)]
+    pub script: bool,
+
+    /// Preview generated commands in a sandbox instead of executing them
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Enter continuous chat mode
     #[arg(short, long)]
     pub chat: bool,
@@ -29,4 +37,8 @@ pub struct Args {
     /// Model name (e.g. "gpt-4.1-mini") - defaults from config.yaml if not specified
     #[arg(short, long)]
     pub model: Option<String>,
+
+    /// Show the environment context sent to the model in shell mode
+    #[arg(long)]
+    pub show_context: bool,
 }
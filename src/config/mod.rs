@@ -61,6 +61,62 @@ pub struct ProviderConfig {
     pub model: Option<String>,
 }
 
+/// Controls which environment details are added to shell-mode prompts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextConfig {
+    pub enabled: bool,
+    pub cwd: bool,
+    pub directory_listing: bool,
+    pub max_entries: usize,
+    pub git: bool,
+    pub package_managers: bool,
+    pub coreutils: bool,
+    pub tools: Vec<String>,
+    pub env_vars: Vec<String>,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cwd: true,
+            directory_listing: false,
+            max_entries: 50,
+            git: true,
+            package_managers: true,
+            coreutils: true,
+            tools: [
+                "rg", "fd", "jq", "docker", "kubectl", "python3", "node", "curl", "wget",
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            env_vars: ["LANG", "TERM", "VIRTUAL_ENV", "CONDA_DEFAULT_ENV"]
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Limits for previewing commands in the sandbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub timeout_secs: u64,
+    pub max_copy_mb: u64,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            max_copy_mb: 200,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpServerConfig {
     pub name: String,
@@ -136,6 +192,10 @@ pub struct Config {
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_mcp_servers")]
     pub mcp_servers: Vec<McpServerConfig>,
+    #[serde(default)]
+    pub context: ContextConfig,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
 }
 
 fn deserialize_mcp_servers<'de, D>(deserializer: D) -> Result<Vec<McpServerConfig>, D::Error>
@@ -204,6 +264,8 @@ impl Config {
             auto_confirm: false,
             providers: HashMap::new(),
             mcp_servers: Vec::new(),
+            context: ContextConfig::default(),
+            sandbox: SandboxConfig::default(),
         };
 
         let _ = config.save();
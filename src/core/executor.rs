@@ -1,9 +1,12 @@
+use crate::config::SandboxConfig;
 use crate::core::error::SchatError;
+use crate::core::sandbox::{self, SandboxReport};
 use crate::display;
 use crate::system::{ShellType, SystemInfo};
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Stdio};
 
-pub fn execute_command(command: &str, system_info: &SystemInfo) -> Result<(), SchatError> {
+/// Executes `command` in the user's shell, displays its output and returns its exit status.
+pub fn execute_command(command: &str, system_info: &SystemInfo) -> Result<ExitStatus, SchatError> {
     let mut cmd = Command::new(&system_info.shell_path);
     match system_info.shell_type {
         ShellType::Cmd => cmd.arg("/C").arg(command),
@@ -22,5 +25,17 @@ pub fn execute_command(command: &str, system_info: &SystemInfo) -> Result<(), Sc
     display::display_stdout(&output.stdout);
     display::display_stderr(&output.stderr);
 
-    Ok(())
+    Ok(output.status)
+}
+
+/// Runs `command` against a throwaway copy of the current directory and displays its output
+/// together with the files it would have created, modified or deleted.
+pub fn preview_command(
+    command: &str,
+    system_info: &SystemInfo,
+    config: &SandboxConfig,
+) -> Result<SandboxReport, SchatError> {
+    let report = sandbox::run_in_sandbox(command, system_info, config)?;
+    display::display_sandbox_report(&report);
+    Ok(report)
 }
@@ -1,2 +1,4 @@
 pub mod error;
 pub mod executor;
+pub mod sandbox;
+pub mod script;
@@ -0,0 +1,324 @@
+use crate::config::SandboxConfig;
+use crate::core::error::SchatError;
+use crate::system::{ShellType, SystemInfo, find_in_path};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Mechanism used to isolate a previewed command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxBackend {
+    /// `bwrap`: read-only root, writable copy of the cwd, no network
+    Bubblewrap,
+    /// `unshare`: private mount and network namespaces, writable copy of the cwd
+    Unshare,
+    /// Plain temporary copy of the cwd without any isolation
+    TempDir,
+}
+
+impl SandboxBackend {
+    /// Picks the strongest isolation available on this system.
+    pub fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return SandboxBackend::TempDir;
+        }
+        if find_in_path("bwrap").is_some() {
+            SandboxBackend::Bubblewrap
+        } else if find_in_path("unshare").is_some() && unshare_works() {
+            SandboxBackend::Unshare
+        } else {
+            SandboxBackend::TempDir
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SandboxBackend::Bubblewrap => "bubblewrap (read-only system, no network)",
+            SandboxBackend::Unshare => "unshare (private mounts, no network)",
+            SandboxBackend::TempDir => {
+                "temporary directory (no isolation, absolute paths are not protected)"
+            }
+        }
+    }
+}
+
+/// A file system change observed in the sandboxed copy of the working directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Deleted(PathBuf),
+}
+
+/// Outcome of running a command in the sandbox
+#[derive(Debug)]
+pub struct SandboxReport {
+    pub backend: SandboxBackend,
+    pub status: Option<ExitStatus>,
+    pub timed_out: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    Dir,
+    File(u64),
+    Symlink(PathBuf),
+}
+
+type Snapshot = BTreeMap<PathBuf, Entry>;
+
+struct RunOutcome {
+    status: Option<ExitStatus>,
+    timed_out: bool,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// Runs `command` against a throwaway copy of the current directory and reports which
+/// files it would have created, modified or deleted.
+pub fn run_in_sandbox(
+    command: &str,
+    system_info: &SystemInfo,
+    config: &SandboxConfig,
+) -> Result<SandboxReport, SchatError> {
+    let cwd = std::env::current_dir()?;
+
+    let mut size = 0;
+    let before = snapshot(&cwd, &mut size)?;
+    if size > config.max_copy_mb * 1024 * 1024 {
+        return Err(SchatError::Execution(format!(
+            "Current directory is larger than {} MB, refusing to copy it into the sandbox",
+            config.max_copy_mb
+        )));
+    }
+
+    let sandbox_dir = std::env::temp_dir().join(format!(
+        "schat-sandbox-{}-{}",
+        std::process::id(),
+        chrono::Local::now().timestamp_millis()
+    ));
+    let work_dir = sandbox_dir.join("work");
+    fs::create_dir_all(&work_dir)?;
+
+    let result = (|| {
+        copy_dir(&cwd, &work_dir)?;
+
+        let backend = SandboxBackend::detect();
+        let mut cmd = sandbox_command(backend, command, system_info, &cwd, &work_dir);
+        let outcome = run_with_timeout(&mut cmd, Duration::from_secs(config.timeout_secs))?;
+
+        let mut ignored = 0;
+        let after = snapshot(&work_dir, &mut ignored)?;
+
+        Ok(SandboxReport {
+            backend,
+            status: outcome.status,
+            timed_out: outcome.timed_out,
+            stdout: outcome.stdout,
+            stderr: outcome.stderr,
+            changes: diff_snapshots(&before, &after),
+        })
+    })();
+
+    let _ = fs::remove_dir_all(&sandbox_dir);
+    result
+}
+
+/// Builds the process that runs `command` with the chosen backend.
+fn sandbox_command(
+    backend: SandboxBackend,
+    command: &str,
+    system_info: &SystemInfo,
+    cwd: &Path,
+    work_dir: &Path,
+) -> Command {
+    let shell_flag = match system_info.shell_type {
+        ShellType::Cmd => "/C",
+        ShellType::PowerShell => "-Command",
+        ShellType::UnixLike | ShellType::Fish => "-c",
+    };
+
+    match backend {
+        SandboxBackend::Bubblewrap => {
+            let mut cmd = Command::new("bwrap");
+            cmd.args(["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"])
+                .args(["--tmpfs", "/tmp"])
+                .arg("--bind")
+                .arg(work_dir)
+                .arg(cwd)
+                .arg("--chdir")
+                .arg(cwd)
+                .args(["--unshare-net", "--unshare-pid", "--die-with-parent"])
+                .args(["--new-session", "--"])
+                .arg(&system_info.shell_path)
+                .arg(shell_flag)
+                .arg(command);
+            cmd
+        }
+        SandboxBackend::Unshare => {
+            let mut cmd = Command::new("unshare");
+            cmd.args(["--user", "--map-root-user", "--net", "--mount"])
+                .args(["--pid", "--fork", "--kill-child", "--"])
+                .args(["sh", "-c"])
+                .arg(r#"mount --bind "$0" "$1" && cd "$1" && exec "$2" "$3" "$4""#)
+                .arg(work_dir)
+                .arg(cwd)
+                .arg(&system_info.shell_path)
+                .arg(shell_flag)
+                .arg(command);
+            cmd
+        }
+        SandboxBackend::TempDir => {
+            let mut cmd = Command::new(&system_info.shell_path);
+            cmd.arg(shell_flag).arg(command).current_dir(work_dir);
+            cmd
+        }
+    }
+}
+
+/// Checks that unprivileged user namespaces are permitted.
+fn unshare_works() -> bool {
+    Command::new("unshare")
+        .args(["--user", "--map-root-user", "--net", "--mount", "true"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Runs `cmd`, killing it if it does not finish within `timeout`.
+fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<RunOutcome, SchatError> {
+    // Run in a separate process group so that a timeout also kills grandchildren that
+    // would otherwise keep the output pipes open.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| SchatError::Execution(format!("Failed to start sandbox: {}", e)))?;
+
+    let mut stdout_pipe = child.stdout.take();
+    let mut stderr_pipe = child.stderr.take();
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (Some(status), false);
+        }
+        if Instant::now() >= deadline {
+            #[cfg(unix)]
+            let _ = Command::new("kill")
+                .args(["-KILL", &format!("-{}", child.id())])
+                .status();
+            let _ = child.kill();
+            let _ = child.wait();
+            break (None, true);
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    Ok(RunOutcome {
+        status,
+        timed_out,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+/// Records every entry below `root`, accumulating the total file size in `size`.
+fn snapshot(root: &Path, size: &mut u64) -> Result<Snapshot, SchatError> {
+    let mut entries = Snapshot::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            let file_type = entry.file_type()?;
+
+            if file_type.is_symlink() {
+                entries.insert(relative, Entry::Symlink(fs::read_link(&path)?));
+            } else if file_type.is_dir() {
+                entries.insert(relative, Entry::Dir);
+                pending.push(path);
+            } else {
+                let content = fs::read(&path)?;
+                *size += content.len() as u64;
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                entries.insert(relative, Entry::File(hasher.finish()));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> Vec<FileChange> {
+    let paths: BTreeSet<&PathBuf> = before.keys().chain(after.keys()).collect();
+
+    paths
+        .into_iter()
+        .filter_map(|path| match (before.get(path), after.get(path)) {
+            (None, Some(_)) => Some(FileChange::Created(path.clone())),
+            (Some(_), None) => Some(FileChange::Deleted(path.clone())),
+            (Some(old), Some(new)) if old != new => Some(FileChange::Modified(path.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Recursively copies `src` into the existing directory `dst`. `fs::copy` uses
+/// copy-on-write clones where the file system supports them.
+fn copy_dir(src: &Path, dst: &Path) -> Result<(), SchatError> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+            #[cfg(not(unix))]
+            fs::copy(entry.path(), &target)?;
+        } else if file_type.is_dir() {
+            fs::create_dir(&target)?;
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::core::error::SchatError;
+use crate::system::ShellType;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A single step of a generated multi-step script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptStep {
+    #[serde(default)]
+    pub description: String,
+    pub command: String,
+}
+
+/// Parses the model's response into an ordered list of steps.
+///
+/// The model is asked for a JSON array of `{"description", "command"}` objects. If the
+/// response is not valid JSON, every non-empty line is treated as a command.
+pub fn parse_script_steps(response: &str) -> Vec<ScriptStep> {
+    let trimmed = response.trim();
+
+    let json = trimmed
+        .find('[')
+        .zip(trimmed.rfind(']'))
+        .filter(|(start, end)| start < end)
+        .map(|(start, end)| &trimmed[start..=end]);
+
+    if let Some(Ok(steps)) = json.map(serde_json::from_str::<Vec<ScriptStep>>) {
+        return steps
+            .into_iter()
+            .filter(|step| !step.command.trim().is_empty())
+            .collect();
+    }
+
+    trimmed
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("```") && !line.starts_with('#'))
+        .map(|line| ScriptStep {
+            description: String::new(),
+            command: strip_list_marker(line).to_string(),
+        })
+        .collect()
+}
+
+/// Removes leading list markers such as `1.`, `-` or `$` from a line.
+fn strip_list_marker(line: &str) -> &str {
+    let without_number = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    let line = if without_number.len() != line.len() {
+        without_number
+            .strip_prefix('.')
+            .or_else(|| without_number.strip_prefix(')'))
+            .unwrap_or(line)
+    } else {
+        line
+    };
+    line.trim_start_matches(['-', '*', '$'])
+        .trim()
+        .trim_matches('`')
+}
+
+/// Renders the steps as a script for the given shell, stopping on the first failure.
+pub fn render_script(steps: &[ScriptStep], shell_type: &ShellType) -> String {
+    let mut script = String::new();
+    match shell_type {
+        ShellType::UnixLike => script.push_str("#!/usr/bin/env bash\nset -e\n"),
+        ShellType::Fish => script.push_str("#!/usr/bin/env fish\n"),
+        ShellType::PowerShell => script.push_str("$ErrorActionPreference = 'Stop'\n"),
+        ShellType::Cmd => script.push_str("@echo off\n"),
+    }
+
+    for step in steps {
+        script.push('\n');
+        if !step.description.is_empty() {
+            let comment = match shell_type {
+                ShellType::Cmd => "REM",
+                _ => "#",
+            };
+            script.push_str(&format!("{} {}\n", comment, step.description));
+        }
+        match shell_type {
+            ShellType::UnixLike => script.push_str(&format!("{}\n", step.command)),
+            ShellType::Fish => script.push_str(&format!("{}\nor exit $status\n", step.command)),
+            ShellType::PowerShell => {
+                script.push_str(&format!("{}\nif (-not $?) {{ exit 1 }}\n", step.command))
+            }
+            ShellType::Cmd => script.push_str(&format!("{} || exit /b 1\n", step.command)),
+        }
+    }
+
+    if matches!(shell_type, ShellType::Cmd) {
+        script = script.replace('\n', "\r\n");
+    }
+    script
+}
+
+/// Writes the rendered script to `path`, making it executable on Unix.
+pub fn save_script(
+    steps: &[ScriptStep],
+    shell_type: &ShellType,
+    path: &Path,
+) -> Result<(), SchatError> {
+    fs::write(path, render_script(steps, shell_type))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::core::sandbox::{FileChange, SandboxReport};
+use crate::core::script::ScriptStep;
 use console::{Emoji, Term, style};
 use termimad::MadSkin;
 
@@ -5,10 +7,28 @@ use termimad::MadSkin;
 #[derive(Debug, PartialEq)]
 pub enum UserChoice {
     Execute,
+    Preview,
     Describe,
     Abort,
 }
 
+/// Represents the user's choice after a multi-step plan is shown.
+#[derive(Debug, PartialEq)]
+pub enum PlanChoice {
+    Run,
+    Save,
+    Abort,
+}
+
+/// Represents the user's choice for a single step of a plan.
+#[derive(Debug, PartialEq)]
+pub enum StepChoice {
+    Run,
+    Skip,
+    Edit,
+    Abort,
+}
+
 /// Display mode for tool interactions
 #[derive(Debug, Clone, Copy)]
 pub enum DisplayMode {
@@ -102,12 +122,24 @@ pub fn display_command(command: &str) {
     println!("{}", command);
 }
 
+/// Display the environment context collected for shell mode
+pub fn display_context(context: &str) {
+    println!("\n{}", style("CONTEXT:").bold().magenta());
+    if context.is_empty() {
+        println!("{}", style("(no context collected)").dim());
+    } else {
+        println!("{}", context);
+    }
+}
+
 /// Ask user for execution confirmation
 pub fn prompt_execution_confirmation() -> UserChoice {
     let term = console::Term::stdout();
     print!(
         "\n{}",
-        style("[E]xecute, [D]escribe, [A]bort: ").bold().cyan()
+        style("[E]xecute, [P]review, [D]escribe, [A]bort: ")
+            .bold()
+            .cyan()
     );
 
     match term.read_line() {
@@ -116,6 +148,9 @@ pub fn prompt_execution_confirmation() -> UserChoice {
             if choice == "e" {
                 term.clear_last_lines(2).ok(); // Clear prompt and input line
                 UserChoice::Execute
+            } else if choice == "p" {
+                term.clear_last_lines(2).ok(); // Clear prompt and input line
+                UserChoice::Preview
             } else if choice == "d" {
                 term.clear_last_lines(2).ok(); // Clear prompt and input line
                 UserChoice::Describe
@@ -131,6 +166,106 @@ pub fn prompt_execution_confirmation() -> UserChoice {
     }
 }
 
+/// Display a generated multi-step plan
+pub fn display_script_plan(steps: &[ScriptStep]) {
+    println!("\n{}", style("PLAN:").bold().magenta());
+    for (i, step) in steps.iter().enumerate() {
+        if step.description.is_empty() {
+            println!("{}. {}", style(i + 1).bold(), step.command);
+        } else {
+            println!(
+                "{}. {}",
+                style(i + 1).bold(),
+                style(&step.description).dim()
+            );
+            println!("   {}", step.command);
+        }
+    }
+}
+
+/// Ask user what to do with a generated plan
+pub fn prompt_plan_confirmation() -> PlanChoice {
+    let term = console::Term::stdout();
+    print!(
+        "\n{}",
+        style("[R]un step by step, [S]ave as script, [A]bort: ")
+            .bold()
+            .cyan()
+    );
+
+    match term.read_line() {
+        Ok(input) => match input.trim().to_lowercase().as_str() {
+            "r" => PlanChoice::Run,
+            "s" => PlanChoice::Save,
+            _ => PlanChoice::Abort,
+        },
+        Err(_) => PlanChoice::Abort,
+    }
+}
+
+/// Display a single step of a plan before it is executed
+pub fn display_script_step(index: usize, total: usize, step: &ScriptStep) {
+    println!(
+        "\n{}",
+        style(format!("STEP {}/{}:", index + 1, total))
+            .bold()
+            .magenta()
+    );
+    if !step.description.is_empty() {
+        println!("{}", style(&step.description).dim());
+    }
+    println!("{}", step.command);
+}
+
+/// Ask user what to do with a single step of a plan
+pub fn prompt_step_confirmation() -> StepChoice {
+    let term = console::Term::stdout();
+    print!(
+        "\n{}",
+        style("[R]un, [S]kip, [E]dit, [A]bort all: ").bold().cyan()
+    );
+
+    match term.read_line() {
+        Ok(input) => match input.trim().to_lowercase().as_str() {
+            "r" => {
+                term.clear_last_lines(2).ok(); // Clear prompt and input line
+                StepChoice::Run
+            }
+            "s" => StepChoice::Skip,
+            "e" => StepChoice::Edit,
+            _ => StepChoice::Abort,
+        },
+        Err(_) => StepChoice::Abort,
+    }
+}
+
+/// Ask user to edit a line of text, pre-filled with `initial`
+pub fn prompt_edit(label: &str, initial: &str) -> Option<String> {
+    let mut editor = rustyline::DefaultEditor::new().ok()?;
+    let prompt = format!("{} ", style(label).bold().cyan());
+    editor
+        .readline_with_initial(&prompt, (initial, ""))
+        .ok()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+/// Display the result of a failed plan step
+pub fn display_step_failure(index: usize, code: Option<i32>) {
+    let code = code.map_or_else(|| "unknown".to_string(), |c| c.to_string());
+    println!(
+        "\n{}{}",
+        CROSS,
+        style(format!(
+            "Step {} failed with exit code {}, stopping.",
+            index + 1,
+            code
+        ))
+        .bold()
+        .red()
+    );
+}
+
 /// Display an AI response in plain text
 pub fn display_response(response: &str) {
     println!("\n{}", style("RESPONSE:").bold().blue());
@@ -157,6 +292,49 @@ pub fn display_stderr(output: &[u8]) {
     }
 }
 
+/// Display the outcome of a sandboxed preview run
+pub fn display_sandbox_report(report: &SandboxReport) {
+    println!(
+        "\n{} {}",
+        style("PREVIEW:").bold().magenta(),
+        style(report.backend.description()).dim()
+    );
+
+    display_stdout(&report.stdout);
+    display_stderr(&report.stderr);
+
+    if report.timed_out {
+        println!(
+            "\n{}{}",
+            CROSS,
+            style("Command timed out and was killed").red()
+        );
+    } else if let Some(status) = report.status.filter(|s| !s.success()) {
+        let code = status
+            .code()
+            .map_or_else(|| "unknown".to_string(), |c| c.to_string());
+        println!("\n{}{}", CROSS, style(format!("Exit code {}", code)).red());
+    }
+
+    println!("\n{}", style("CHANGES:").bold().blue());
+    if report.changes.is_empty() {
+        println!("{}", style("No files would be changed").dim());
+    }
+    for change in &report.changes {
+        match change {
+            FileChange::Created(path) => {
+                println!("{}", style(format!("+ {}", path.display())).green())
+            }
+            FileChange::Modified(path) => {
+                println!("{}", style(format!("~ {}", path.display())).yellow())
+            }
+            FileChange::Deleted(path) => {
+                println!("{}", style(format!("- {}", path.display())).red())
+            }
+        }
+    }
+}
+
 /// Display tool call information (respects display mode)
 pub fn display_tool_call(tool_name: &str) {
     match get_display_mode() {
@@ -1,6 +1,9 @@
+use crate::config::ContextConfig;
 use os_info;
 use std::env;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 /// Represents different shell types with their specific command arguments
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,6 +14,18 @@ pub enum ShellType {
     Fish,       // Fish shell
 }
 
+impl ShellType {
+    /// File extension used for scripts written for this shell
+    pub fn script_extension(&self) -> &'static str {
+        match self {
+            ShellType::Cmd => "bat",
+            ShellType::PowerShell => "ps1",
+            ShellType::UnixLike => "sh",
+            ShellType::Fish => "fish",
+        }
+    }
+}
+
 /// Holds information about the current system environment
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
@@ -76,3 +91,230 @@ fn detect_shell() -> (String, ShellType) {
         }
     }
 }
+
+/// Flavor of the core utilities (`ls`, `sed`, `grep`, ...) found on the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreutilsFlavor {
+    Gnu,
+    Bsd,
+    Busybox,
+}
+
+impl CoreutilsFlavor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoreutilsFlavor::Gnu => "GNU",
+            CoreutilsFlavor::Bsd => "BSD",
+            CoreutilsFlavor::Busybox => "BusyBox",
+        }
+    }
+}
+
+/// Package managers that are looked up on `PATH`
+const PACKAGE_MANAGERS: &[&str] = &[
+    "apt", "dnf", "yum", "pacman", "zypper", "apk", "emerge", "nix", "brew", "port", "pkg",
+    "winget", "choco", "scoop",
+];
+
+/// Optional details about the environment that help the model tailor shell commands
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentContext {
+    pub cwd: Option<String>,
+    pub directory_listing: Vec<String>,
+    pub git_branch: Option<String>,
+    pub git_status: Option<String>,
+    pub package_managers: Vec<String>,
+    pub available_tools: Vec<String>,
+    pub missing_tools: Vec<String>,
+    pub coreutils: Option<CoreutilsFlavor>,
+    pub env_vars: Vec<(String, String)>,
+}
+
+impl EnvironmentContext {
+    /// Collects the pieces of context enabled in `config`.
+    pub fn collect(config: &ContextConfig) -> Self {
+        let mut context = EnvironmentContext::default();
+        if !config.enabled {
+            return context;
+        }
+
+        let cwd = env::current_dir().ok();
+
+        if config.cwd {
+            context.cwd = cwd.as_ref().map(|p| p.display().to_string());
+        }
+
+        if let Some(dir) = cwd.as_ref().filter(|_| config.directory_listing) {
+            context.directory_listing = list_directory(dir, config.max_entries);
+        }
+
+        if config.git && find_in_path("git").is_some() {
+            context.git_branch = run_and_capture("git", &["rev-parse", "--abbrev-ref", "HEAD"]);
+            context.git_status =
+                run_and_capture("git", &["status", "--porcelain"]).map(|s| {
+                    match s.lines().count() {
+                        0 => "clean".to_string(),
+                        1 => "1 changed file".to_string(),
+                        n => format!("{} changed files", n),
+                    }
+                });
+        }
+
+        if config.package_managers {
+            context.package_managers = PACKAGE_MANAGERS
+                .iter()
+                .filter(|pm| find_in_path(pm).is_some())
+                .map(|pm| pm.to_string())
+                .collect();
+        }
+
+        for tool in &config.tools {
+            if find_in_path(tool).is_some() {
+                context.available_tools.push(tool.clone());
+            } else {
+                context.missing_tools.push(tool.clone());
+            }
+        }
+
+        if config.coreutils {
+            context.coreutils = detect_coreutils();
+        }
+
+        context.env_vars = config
+            .env_vars
+            .iter()
+            .filter_map(|name| env::var(name).ok().map(|value| (name.clone(), value)))
+            .collect();
+
+        context
+    }
+
+    /// Renders the context as a bullet list suitable for a system prompt.
+    /// Returns an empty string when nothing was collected.
+    pub fn to_prompt(&self) -> String {
+        let mut lines = Vec::new();
+
+        if let Some(cwd) = &self.cwd {
+            lines.push(format!("- Current directory: {}", cwd));
+        }
+        if !self.directory_listing.is_empty() {
+            lines.push(format!(
+                "- Directory contents: {}",
+                self.directory_listing.join(", ")
+            ));
+        }
+        if let Some(branch) = &self.git_branch {
+            let status = self.git_status.as_deref().unwrap_or("unknown");
+            lines.push(format!(
+                "- Git repository on branch {} ({})",
+                branch, status
+            ));
+        }
+        if !self.package_managers.is_empty() {
+            lines.push(format!(
+                "- Package managers: {}",
+                self.package_managers.join(", ")
+            ));
+        }
+        if !self.available_tools.is_empty() {
+            lines.push(format!(
+                "- Installed tools: {}",
+                self.available_tools.join(", ")
+            ));
+        }
+        if !self.missing_tools.is_empty() {
+            lines.push(format!(
+                "- Not installed (do not use): {}",
+                self.missing_tools.join(", ")
+            ));
+        }
+        if let Some(flavor) = self.coreutils {
+            lines.push(format!("- Core utilities: {}", flavor.as_str()));
+        }
+        for (name, value) in &self.env_vars {
+            lines.push(format!("- ${}={}", name, value));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the full path of `program` if it can be found on `PATH`.
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(program);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if cfg!(target_os = "windows") {
+            for ext in ["exe", "cmd", "bat"] {
+                let candidate = candidate.with_extension(ext);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Runs a program and returns its trimmed stdout if it exited successfully.
+fn run_and_capture(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Lists the non-hidden entries of `dir`, marking directories with a trailing slash.
+fn list_directory(dir: &Path, max_entries: usize) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                return None;
+            }
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            Some(if is_dir { format!("{}/", name) } else { name })
+        })
+        .collect();
+    names.sort();
+
+    if names.len() > max_entries {
+        let remaining = names.len() - max_entries;
+        names.truncate(max_entries);
+        names.push(format!("... and {} more", remaining));
+    }
+    names
+}
+
+/// Detects which implementation of the core utilities is installed.
+fn detect_coreutils() -> Option<CoreutilsFlavor> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+
+    let ls = find_in_path("ls")?;
+    let target = fs::canonicalize(&ls).unwrap_or(ls);
+    if target.file_name().and_then(|n| n.to_str()) == Some("busybox") {
+        return Some(CoreutilsFlavor::Busybox);
+    }
+
+    match run_and_capture("ls", &["--version"]) {
+        Some(version) if version.contains("GNU") => Some(CoreutilsFlavor::Gnu),
+        Some(version) if version.contains("BusyBox") => Some(CoreutilsFlavor::Busybox),
+        _ => Some(CoreutilsFlavor::Bsd),
+    }
+}